@@ -1,19 +1,152 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 
+use std::sync::Mutex;
+
+use serde::Deserialize;
 use tauri::{Emitter, Manager};
 use tauri::menu::{MenuBuilder, MenuItem, PredefinedMenuItem};
+use tauri::tray::{TrayIconBuilder, TrayIconEvent};
+
+// Tracks the "menubar-only" preference: whether the Dock icon should be
+// hidden while AttentionScreen is running with no window visible, along
+// with the window that last asked for a dynamic context menu.
+struct AppState {
+    menubar_only: Mutex<bool>,
+    context_menu_window: Mutex<Option<String>>,
+}
+
+// Ids handed to `MenuItem::with_id` for entries built by `show_context_menu`
+// carry this prefix so `on_menu_event` can tell them apart from the menu
+// bar's own "open_settings" / "open_window" ids (which a frontend entry
+// could otherwise collide with) without guessing.
+const CONTEXT_MENU_ID_PREFIX: &str = "ctx:";
+
+// On-disk form of the preferences above, so they survive a restart.
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct PersistedSettings {
+    menubar_only: bool,
+}
+
+fn settings_file_path(app: &tauri::AppHandle) -> Option<std::path::PathBuf> {
+    app.path()
+        .app_config_dir()
+        .ok()
+        .map(|dir| dir.join("settings.json"))
+}
+
+fn load_menubar_only(app: &tauri::AppHandle) -> bool {
+    settings_file_path(app)
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str::<PersistedSettings>(&contents).ok())
+        .map(|settings| settings.menubar_only)
+        .unwrap_or(false)
+}
+
+fn save_menubar_only(app: &tauri::AppHandle, enabled: bool) {
+    let Some(path) = settings_file_path(app) else {
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string(&PersistedSettings { menubar_only: enabled }) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+// One entry in a context menu requested by the frontend, so its contents
+// can vary with current app state instead of being hard-coded in Rust.
+#[derive(Deserialize)]
+struct ContextMenuEntry {
+    id: String,
+    label: String,
+    enabled: bool,
+    accelerator: Option<String>,
+}
 
 #[tauri::command]
 fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
+#[tauri::command]
+fn show_context_menu(window: tauri::Window, entries: Vec<ContextMenuEntry>) -> tauri::Result<()> {
+    let app = window.app_handle();
+    *app.state::<AppState>().context_menu_window.lock().unwrap() = Some(window.label().to_string());
+
+    let mut builder = MenuBuilder::new(app);
+    for entry in entries {
+        let item = MenuItem::with_id(
+            app,
+            format!("{CONTEXT_MENU_ID_PREFIX}{}", entry.id),
+            entry.label,
+            entry.enabled,
+            entry.accelerator.as_deref(),
+        )?;
+        builder = builder.item(&item);
+    }
+    let menu = builder.build()?;
+    window.popup_menu(&menu)?;
+    Ok(())
+}
+
+#[tauri::command]
+fn set_menubar_only(app: tauri::AppHandle, enabled: bool) {
+    let state = app.state::<AppState>();
+    *state.menubar_only.lock().unwrap() = enabled;
+    save_menubar_only(&app, enabled);
+
+    apply_activation_policy(&app, enabled, has_visible_main_window(&app));
+
+    if let Some(win) = app.get_webview_window("main") {
+        let _ = win.emit("settings:menubar-only-changed", enabled);
+    }
+}
+
+fn has_visible_main_window(app: &tauri::AppHandle) -> bool {
+    app.get_webview_window("main")
+        .map(|win| win.is_visible().unwrap_or(false))
+        .unwrap_or(false)
+}
+
+#[cfg(target_os = "macos")]
+fn apply_activation_policy(app: &tauri::AppHandle, menubar_only: bool, has_visible_window: bool) {
+    let policy = if menubar_only && !has_visible_window {
+        tauri::ActivationPolicy::Accessory
+    } else {
+        tauri::ActivationPolicy::Regular
+    };
+    let _ = app.set_activation_policy(policy);
+}
+
+#[cfg(not(target_os = "macos"))]
+fn apply_activation_policy(_app: &tauri::AppHandle, _menubar_only: bool, _has_visible_window: bool) {}
+
+fn show_main_window(app: &tauri::AppHandle) {
+    if let Some(win) = app.get_webview_window("main") {
+        let _ = win.show();
+        let _ = win.set_focus();
+    }
+    let menubar_only = *app.state::<AppState>().menubar_only.lock().unwrap();
+    apply_activation_policy(app, menubar_only, true);
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .invoke_handler(tauri::generate_handler![greet])
+        .invoke_handler(tauri::generate_handler![greet, set_menubar_only, show_context_menu])
+        .manage(AppState {
+            menubar_only: Mutex::new(false),
+            context_menu_window: Mutex::new(None),
+        })
         .setup(|app| {
+            // Restore the menubar-only preference saved on a previous run
+            // and apply it before any window is shown.
+            let menubar_only = load_menubar_only(app.handle());
+            *app.state::<AppState>().menubar_only.lock().unwrap() = menubar_only;
+            apply_activation_policy(app.handle(), menubar_only, has_visible_main_window(app.handle()));
+
             #[cfg(target_os = "macos")]
             {
                 use tauri::menu::{MenuBuilder, MenuItem, SubmenuBuilder, PredefinedMenuItem};
@@ -69,17 +202,172 @@ pub fn run() {
         
                 app.set_menu(menu)?;
             }
-        
+
+            #[cfg(target_os = "windows")]
+            {
+                use tauri::menu::{MenuBuilder, SubmenuBuilder, PredefinedMenuItem};
+
+                let settings = MenuItem::with_id(
+                    app,
+                    "open_settings",
+                    "Settings…",
+                    true,
+                    Some("CmdOrCtrl+,"),
+                )?;
+
+                // File submenu
+                let file_submenu = SubmenuBuilder::new(app, "File")
+                    .item(&settings)
+                    .separator()
+                    .item(&PredefinedMenuItem::close_window(app, None)?)
+                    .item(&PredefinedMenuItem::quit(app, None)?)
+                    .build()?;
+
+                // Edit submenu (standard)
+                let edit_submenu = SubmenuBuilder::new(app, "Edit")
+                    .item(&PredefinedMenuItem::cut(app, None)?)
+                    .item(&PredefinedMenuItem::copy(app, None)?)
+                    .item(&PredefinedMenuItem::paste(app, None)?)
+                    .item(&PredefinedMenuItem::select_all(app, None)?)
+                    .build()?;
+
+                // Window submenu (standard)
+                let window_submenu = SubmenuBuilder::new(app, "Window")
+                    .item(&PredefinedMenuItem::minimize(app, None)?)
+                    .item(&PredefinedMenuItem::close_window(app, None)?)
+                    .build()?;
+
+                // Build full menu bar
+                let menu = MenuBuilder::new(app)
+                    .item(&file_submenu)
+                    .item(&edit_submenu)
+                    .item(&window_submenu)
+                    .build()?;
+
+                app.set_menu(menu)?;
+            }
+
+            #[cfg(target_os = "linux")]
+            {
+                use tauri::menu::{MenuBuilder, SubmenuBuilder, PredefinedMenuItem};
+
+                let settings = MenuItem::with_id(
+                    app,
+                    "open_settings",
+                    "Settings…",
+                    true,
+                    Some("CmdOrCtrl+,"),
+                )?;
+
+                // File submenu
+                let file_submenu = SubmenuBuilder::new(app, "File")
+                    .item(&settings)
+                    .separator()
+                    .item(&PredefinedMenuItem::close_window(app, None)?)
+                    .item(&PredefinedMenuItem::quit(app, None)?)
+                    .build()?;
+
+                // Window submenu (standard)
+                let window_submenu = SubmenuBuilder::new(app, "Window")
+                    .item(&PredefinedMenuItem::minimize(app, None)?)
+                    .item(&PredefinedMenuItem::close_window(app, None)?)
+                    .build()?;
+
+                // Build full menu bar
+                let menu = MenuBuilder::new(app)
+                    .item(&file_submenu)
+                    .item(&window_submenu)
+                    .build()?;
+
+                app.set_menu(menu)?;
+            }
+
+            // Tray icon so the app keeps running and stays reachable even when
+            // every window has been closed.
+            let tray_open = MenuItem::with_id(app, "open_window", "Open AttentionScreen", true, None::<&str>)?;
+            let tray_settings = MenuItem::with_id(app, "open_settings", "Settings…", true, None::<&str>)?;
+            let tray_menu = MenuBuilder::new(app)
+                .item(&tray_open)
+                .item(&tray_settings)
+                .separator()
+                .item(&PredefinedMenuItem::quit(app, None)?)
+                .build()?;
+
+            TrayIconBuilder::new()
+                .icon(app.default_window_icon().unwrap().clone())
+                .menu(&tray_menu)
+                .show_menu_on_left_click(false)
+                .on_tray_icon_event(|tray, event| {
+                    if let TrayIconEvent::Click {
+                        button: tauri::tray::MouseButton::Left,
+                        button_state: tauri::tray::MouseButtonState::Up,
+                        ..
+                    } = event
+                    {
+                        let app = tray.app_handle();
+                        if has_visible_main_window(app) {
+                            if let Some(win) = app.get_webview_window("main") {
+                                let _ = win.hide();
+                            }
+                            let menubar_only = *app.state::<AppState>().menubar_only.lock().unwrap();
+                            apply_activation_policy(app, menubar_only, false);
+                        } else {
+                            show_main_window(app);
+                        }
+                    }
+                })
+                .build(app)?;
+
             Ok(())
         })
-        
+
         .on_menu_event(|app, event| {
-            if event.id().as_ref() == "open_settings" {
-                if let Some(win) = app.get_webview_window("main") {
-                    let _ = win.emit("menu:open-settings", ());
+            let id = event.id().as_ref();
+            if let Some(item_id) = id.strip_prefix(CONTEXT_MENU_ID_PREFIX) {
+                // Forward the selection to whichever window asked for this
+                // popup, not every item the menu bar / tray also happens to use.
+                let target = app.state::<AppState>().context_menu_window.lock().unwrap().clone();
+                if let Some(win) = target.and_then(|label| app.get_webview_window(&label)) {
+                    let _ = win.emit("context-menu:item-selected", item_id);
+                }
+                return;
+            }
+
+            match id {
+                "open_settings" => {
+                    show_main_window(app);
+                    if let Some(win) = app.get_webview_window("main") {
+                        let _ = win.emit("menu:open-settings", ());
+                    }
+                }
+                "open_window" => {
+                    show_main_window(app);
                 }
+                _ => {}
             }
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                // Keep AttentionScreen alive in the tray/menubar instead of
+                // quitting when the last window is closed.
+                api.prevent_close();
+                let _ = window.hide();
+
+                let app = window.app_handle();
+                let menubar_only = *app.state::<AppState>().menubar_only.lock().unwrap();
+                apply_activation_policy(app, menubar_only, false);
+            }
+        })
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app, event| {
+            // Clicking the Dock icon while every window is hidden should
+            // bring AttentionScreen back, the same as picking it from the tray.
+            #[cfg(target_os = "macos")]
+            {
+                if let tauri::RunEvent::Reopen { .. } = event {
+                    show_main_window(app);
+                }
+            }
+        });
 }